@@ -0,0 +1,76 @@
+//! Field constants and small big-integer helpers for the alt_bn128
+//! (BN254) curve, shared by the single-proof and batch verification
+//! paths.
+
+/// Order of the BN254 scalar field (`Fr`), i.e. the size of the group
+/// that proof/public-input scalars live in. Used to reduce hash output
+/// into a valid random linear-combination coefficient.
+pub const FR_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// Order of the BN254 base field (`Fq`), used for negating a G1 point's
+/// y-coordinate.
+pub const FQ_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Reduces a 32-byte big-endian digest modulo the BN254 scalar field,
+/// yielding a valid batching coefficient. `big_mod_exp` with an
+/// exponent of 1 computes `digest mod modulus` with the
+/// multiprecision modular-exponentiation syscall.
+pub fn reduce_mod_fr(digest: &[u8; 32]) -> [u8; 32] {
+    let reduced = solana_program::big_mod_exp::big_mod_exp(digest, &[1], &FR_MODULUS);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&reduced);
+    out
+}
+
+/// Negates a G1 point (`x`, `y`) by computing `y' = FQ_MODULUS - y`.
+/// Returns the point unchanged if `y` is zero, since `-0 == 0`.
+pub fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut out = *point;
+    if point[32..64].iter().all(|b| *b == 0) {
+        return out;
+    }
+
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let a = FQ_MODULUS[i] as i16;
+        let b = point[32 + i] as i16;
+        let mut diff = a - b - borrow;
+        if diff < 0 {
+            diff += 256;
+            borrow = 1;
+        } else {
+            borrow = 0;
+        }
+        out[32 + i] = diff as u8;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negate_g1_is_an_involution() {
+        let mut point = [0u8; 64];
+        point[0] = 1; // x = 1
+        point[63] = 7; // y = 7, some nonzero value less than FQ_MODULUS
+
+        let negated = negate_g1(&point);
+        assert_ne!(negated, point);
+        assert_eq!(negated[0..32], point[0..32]); // x is unchanged
+        assert_eq!(negate_g1(&negated), point);
+    }
+
+    #[test]
+    fn negate_g1_leaves_the_zero_point_unchanged() {
+        let zero = [0u8; 64];
+        assert_eq!(negate_g1(&zero), zero);
+    }
+}
@@ -0,0 +1,38 @@
+use pinocchio::program_error::ProgramError;
+
+/// Errors specific to the verifier program, surfaced through
+/// `ProgramError::Custom`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifierError {
+    /// The Groth16 pairing check did not hold.
+    ProofVerificationFailed = 0,
+    /// A verification key account did not match the PDA derived from the
+    /// circuit identifier supplied in the instruction.
+    VerificationKeyAccountMismatch = 1,
+    /// The verification key account does not belong to this program, is
+    /// not writable or large enough for `InitializeVk`, or does not hold
+    /// a well-formed key; also returned for a key with no `vk_ic` points.
+    InvalidVerificationKeyAccount = 2,
+    /// `nullifier_index` did not name one of the supplied public inputs.
+    NullifierIndexOutOfRange = 3,
+    /// The nullifier account did not match the PDA derived from the
+    /// circuit identifier and the designated public input.
+    NullifierAccountMismatch = 4,
+    /// The nullifier has already been spent by a previous `Verify`.
+    NullifierAlreadySpent = 5,
+    /// `instruction_data`'s version byte is not one this program understands.
+    UnsupportedInstructionVersion = 6,
+    /// `instruction_data`'s length doesn't match what its own header
+    /// counts say it should be.
+    InstructionLengthMismatch = 7,
+    /// The declared public-input count is inconsistent with the
+    /// verification key's `vk_ic` length (`public_input_count + 1` must
+    /// equal `vk_ic.len()`).
+    PublicInputCountMismatch = 8,
+}
+
+impl From<VerifierError> for ProgramError {
+    fn from(e: VerifierError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,346 @@
+use pinocchio::{program_error::ProgramError, ProgramResult};
+
+use crate::bn254::reduce_mod_fr;
+use crate::error::VerifierError;
+use crate::state::VerificationKey;
+
+pub struct NoirProof {
+    pub proof_a: [u8; 64],
+    pub proof_b: [u8; 128],
+    pub proof_c: [u8; 64],
+}
+
+pub struct PublicInputs(pub Vec<[u8; 32]>);
+
+pub fn parse_proof(data: &[u8]) -> Result<NoirProof, ProgramError> {
+    if data.len() != 256 {
+        return Err(VerifierError::InstructionLengthMismatch.into());
+    }
+    let proof_a = data[0..64]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let proof_b = data[64..192]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let proof_c = data[192..256]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    Ok(NoirProof {
+        proof_a,
+        proof_b,
+        proof_c,
+    })
+}
+
+/// Parses exactly `count` 32-byte public inputs out of `data`, so the
+/// caller's declared count and the instruction's actual length are
+/// cross-checked rather than inferred from one another.
+pub fn parse_public_inputs(data: &[u8], count: u16) -> Result<PublicInputs, ProgramError> {
+    if data.len() != count as usize * 32 {
+        return Err(VerifierError::InstructionLengthMismatch.into());
+    }
+
+    let public_inputs = data
+        .chunks(32)
+        .map(|chunk| {
+            chunk
+                .try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)
+        })
+        .collect::<Result<Vec<[u8; 32]>, ProgramError>>()?;
+
+    Ok(PublicInputs(public_inputs))
+}
+
+/// Binds an arbitrary-length blob of application data to exactly one
+/// Groth16 public input, matching how a Noir circuit exposes a single
+/// `std::hash` commitment instead of many field elements: hash
+/// `application_data` with the blake3 syscall and reduce the digest
+/// (interpreted big-endian) modulo the BN254 scalar field.
+pub fn commitment_public_inputs(application_data: &[u8]) -> PublicInputs {
+    let digest = solana_program::blake3::hashv(&[application_data]);
+    PublicInputs(vec![reduce_mod_fr(&digest.to_bytes())])
+}
+
+/// Splits the raw `proofs_data` payload of a `VerifyBatch` instruction
+/// into `num_proofs` proofs, each followed by `num_public_inputs`
+/// field elements. This happens in the processor rather than in
+/// `VerifierInstruction::unpack`, once the instruction's declared
+/// public-input count has been checked against the loaded VK's `vk_ic`
+/// length.
+pub fn split_batch(
+    data: &[u8],
+    num_proofs: u16,
+    num_public_inputs: usize,
+) -> Result<(Vec<NoirProof>, Vec<PublicInputs>), ProgramError> {
+    let entry_len = 256 + num_public_inputs * 32;
+    let num_proofs = num_proofs as usize;
+    if num_proofs == 0 || data.len() != entry_len * num_proofs {
+        return Err(VerifierError::InstructionLengthMismatch.into());
+    }
+
+    let mut proofs = Vec::with_capacity(num_proofs);
+    let mut public_inputs = Vec::with_capacity(num_proofs);
+    for entry in data.chunks(entry_len) {
+        proofs.push(parse_proof(&entry[..256])?);
+        public_inputs.push(parse_public_inputs(
+            &entry[256..],
+            num_public_inputs as u16,
+        )?);
+    }
+
+    Ok((proofs, public_inputs))
+}
+
+pub fn prepare_public_inputs(
+    inputs: &PublicInputs,
+    vk: &VerificationKey,
+) -> Result<[u8; 64], ProgramError> {
+    if inputs.0.len() + 1 != vk.vk_ic.len() {
+        return Err(VerifierError::PublicInputCountMismatch.into());
+    }
+
+    let mut prepared_inputs = vk.vk_ic[0];
+    for (i, input) in inputs.0.iter().enumerate() {
+        let mul_res = solana_program::alt_bn128::prelude::alt_bn128_multiplication(
+            &[&vk.vk_ic[i + 1][..], &input[..]].concat(),
+        )
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        prepared_inputs = solana_program::alt_bn128::prelude::alt_bn128_addition(
+            &[&mul_res[..], &prepared_inputs[..]].concat(),
+        )
+        .map_err(|_| ProgramError::InvalidInstructionData)?[..]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+    }
+
+    Ok(prepared_inputs)
+}
+
+pub fn verify_proof(
+    proof: &NoirProof,
+    prepared_inputs: &[u8; 64],
+    vk: &VerificationKey,
+) -> ProgramResult {
+    let pairing_input = [
+        proof.proof_a.as_slice(),
+        proof.proof_b.as_slice(),
+        prepared_inputs.as_slice(),
+        vk.vk_gamma_g2.as_slice(),
+        proof.proof_c.as_slice(),
+        vk.vk_delta_g2.as_slice(),
+        vk.vk_alpha_g1.as_slice(),
+        vk.vk_beta_g2.as_slice(),
+    ]
+    .concat();
+
+    let pairing_res = solana_program::alt_bn128::prelude::alt_bn128_pairing(&pairing_input)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    if pairing_res[31] != 1 {
+        return Err(VerifierError::ProofVerificationFailed.into());
+    }
+
+    Ok(())
+}
+
+fn ec_add(a: &[u8; 64], b: &[u8; 64]) -> Result<[u8; 64], ProgramError> {
+    solana_program::alt_bn128::prelude::alt_bn128_addition(&[&a[..], &b[..]].concat())
+        .map_err(|_| ProgramError::InvalidInstructionData)?[..]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+fn ec_scalar_mul(point: &[u8; 64], scalar: &[u8; 32]) -> Result<[u8; 64], ProgramError> {
+    solana_program::alt_bn128::prelude::alt_bn128_multiplication(
+        &[&point[..], &scalar[..]].concat(),
+    )
+    .map_err(|_| ProgramError::InvalidInstructionData)?[..]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+/// Derives the random linear-combination coefficient `r_i` for proof
+/// `index` in a batch by hashing its proof and public-input bytes with
+/// the blake3 syscall and reducing the digest modulo the BN254 scalar
+/// field.
+fn batch_coefficient(index: u32, proof: &NoirProof, inputs: &PublicInputs) -> [u8; 32] {
+    let public_input_bytes: Vec<u8> = inputs.0.iter().flatten().copied().collect();
+    let digest = solana_program::blake3::hashv(&[
+        &index.to_le_bytes(),
+        proof.proof_a.as_slice(),
+        proof.proof_b.as_slice(),
+        proof.proof_c.as_slice(),
+        &public_input_bytes,
+    ]);
+    reduce_mod_fr(&digest.to_bytes())
+}
+
+/// Verifies `N` Groth16 proofs sharing one verification key with a
+/// single pairing check, using a random linear combination to collapse
+/// `N` independent pairings into one. `verify_proof`'s single-proof
+/// check is `e(A,B) * e(alpha,beta) * e(PI,gamma) * e(C,delta) == 1`
+/// with none of the four terms negated (proof generation is assumed to
+/// supply an already-negated `A`), so scaling proof `i` by a random
+/// `r_i` and accumulating must preserve that same sign convention:
+///
+/// `prod_i e(r_i*A_i, B_i) * e(sum_i(r_i*alpha), beta)
+///     * e(sum_i(r_i*PI_i), gamma) * e(sum_i(r_i*C_i), delta) == 1`.
+///
+/// `N == 1` falls back to `verify_proof` directly since there is
+/// nothing to batch, and also serves as the reference this formula must
+/// reduce to when `r_1 == 1`.
+pub fn verify_batch(
+    proofs: &[NoirProof],
+    public_inputs: &[PublicInputs],
+    vk: &VerificationKey,
+) -> ProgramResult {
+    if proofs.is_empty() || proofs.len() != public_inputs.len() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if proofs.len() == 1 {
+        let prepared = prepare_public_inputs(&public_inputs[0], vk)?;
+        return verify_proof(&proofs[0], &prepared, vk);
+    }
+
+    let mut scaled_a_b = Vec::with_capacity(proofs.len());
+    let mut alpha_acc = [0u8; 64];
+    let mut pi_acc = [0u8; 64];
+    let mut c_acc = [0u8; 64];
+
+    for (i, (proof, inputs)) in proofs.iter().zip(public_inputs.iter()).enumerate() {
+        let r_i = batch_coefficient(i as u32, proof, inputs);
+        if r_i == [0u8; 32] {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        scaled_a_b.push((ec_scalar_mul(&proof.proof_a, &r_i)?, proof.proof_b));
+
+        alpha_acc = ec_add(&alpha_acc, &ec_scalar_mul(&vk.vk_alpha_g1, &r_i)?)?;
+
+        let prepared = prepare_public_inputs(inputs, vk)?;
+        pi_acc = ec_add(&pi_acc, &ec_scalar_mul(&prepared, &r_i)?)?;
+
+        c_acc = ec_add(&c_acc, &ec_scalar_mul(&proof.proof_c, &r_i)?)?;
+    }
+
+    let mut pairing_input = Vec::with_capacity(scaled_a_b.len() * 192 + 3 * 192);
+    for (a, b) in &scaled_a_b {
+        pairing_input.extend_from_slice(a);
+        pairing_input.extend_from_slice(b);
+    }
+    pairing_input.extend_from_slice(&alpha_acc);
+    pairing_input.extend_from_slice(&vk.vk_beta_g2);
+    pairing_input.extend_from_slice(&pi_acc);
+    pairing_input.extend_from_slice(&vk.vk_gamma_g2);
+    pairing_input.extend_from_slice(&c_acc);
+    pairing_input.extend_from_slice(&vk.vk_delta_g2);
+
+    let pairing_res = solana_program::alt_bn128::prelude::alt_bn128_pairing(&pairing_input)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    if pairing_res[31] != 1 {
+        return Err(VerifierError::ProofVerificationFailed.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_proof_rejects_wrong_length() {
+        assert!(parse_proof(&[0u8; 255]).is_err());
+        assert!(parse_proof(&[0u8; 257]).is_err());
+        assert!(parse_proof(&[0u8; 256]).is_ok());
+    }
+
+    #[test]
+    fn parse_public_inputs_rejects_length_mismatch() {
+        assert!(parse_public_inputs(&[0u8; 32], 2).is_err());
+        assert!(parse_public_inputs(&[0u8; 64], 2).is_ok());
+    }
+
+    #[test]
+    fn split_batch_rejects_zero_proofs() {
+        assert!(split_batch(&[], 0, 1).is_err());
+    }
+
+    #[test]
+    fn split_batch_rejects_wrong_total_length() {
+        // One proof with one public input is 256 + 32 = 288 bytes; claim
+        // two proofs' worth of that same buffer.
+        assert!(split_batch(&[0u8; 288], 2, 1).is_err());
+    }
+
+    #[test]
+    fn verify_batch_rejects_mismatched_proof_and_input_counts() {
+        let vk = VerificationKey {
+            vk_alpha_g1: [0u8; 64],
+            vk_beta_g2: [0u8; 128],
+            vk_gamma_g2: [0u8; 128],
+            vk_delta_g2: [0u8; 128],
+            vk_ic: vec![[0u8; 64]; 2],
+        };
+        let proof = NoirProof {
+            proof_a: [0u8; 64],
+            proof_b: [0u8; 128],
+            proof_c: [0u8; 64],
+        };
+        let inputs = PublicInputs(vec![[0u8; 32]]);
+
+        assert!(verify_batch(&[proof], &[inputs, PublicInputs(vec![[0u8; 32]])], &vk).is_err());
+    }
+
+    /// An all-identity VK and proof (every group element the point at
+    /// infinity, no public inputs) makes `e(A,B)*e(alpha,beta)*e(PI,gamma)*e(C,delta)`
+    /// trivially equal to 1, since every pairing with the infinity point
+    /// is itself the identity. This is the
+    /// smallest proof/VK pair that passes `verify_proof`, so it lets the
+    /// `N>1` accumulation path be exercised without real trusted-setup
+    /// material.
+    fn trivial_vk_and_proof() -> (VerificationKey, NoirProof) {
+        let vk = VerificationKey {
+            vk_alpha_g1: [0u8; 64],
+            vk_beta_g2: [0u8; 128],
+            vk_gamma_g2: [0u8; 128],
+            vk_delta_g2: [0u8; 128],
+            vk_ic: vec![[0u8; 64]],
+        };
+        let proof = NoirProof {
+            proof_a: [0u8; 64],
+            proof_b: [0u8; 128],
+            proof_c: [0u8; 64],
+        };
+        (vk, proof)
+    }
+
+    #[test]
+    fn verify_batch_n_equals_one_matches_verify_proof() {
+        let (vk, proof) = trivial_vk_and_proof();
+        let inputs = PublicInputs(vec![]);
+
+        let prepared = prepare_public_inputs(&inputs, &vk).unwrap();
+        assert!(verify_proof(&proof, &prepared, &vk).is_ok());
+        assert!(verify_batch(&[proof], &[inputs], &vk).is_ok());
+    }
+
+    #[test]
+    fn verify_batch_n_greater_than_one_accepts_proofs_individually_valid() {
+        let (vk, proof0) = trivial_vk_and_proof();
+        let (_, proof1) = trivial_vk_and_proof();
+        let inputs0 = PublicInputs(vec![]);
+        let inputs1 = PublicInputs(vec![]);
+
+        // The N>1 accumulation must reduce to the same accept/reject
+        // outcome as verifying each proof individually, not flip the
+        // sign of the accumulated alpha/PI/C terms relative to
+        // `verify_proof`'s convention.
+        assert!(verify_batch(&[proof0, proof1], &[inputs0, inputs1], &vk).is_ok());
+    }
+}
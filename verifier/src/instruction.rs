@@ -0,0 +1,416 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::error::VerifierError;
+use crate::groth16::{parse_proof, parse_public_inputs, NoirProof, PublicInputs};
+use crate::state::VerificationKey;
+
+/// A circuit identifier is an arbitrary 32-byte tag chosen by whoever
+/// initializes a verification key; it is the seed used to derive that
+/// key's PDA.
+pub type CircuitId = [u8; 32];
+
+/// The only `instruction_data` layout version this program understands.
+/// Bumping it is how a future, incompatible header shape would be
+/// rolled out without misparsing old clients' instructions.
+pub const INSTRUCTION_VERSION: u8 = 1;
+
+pub enum VerifierInstruction {
+    /// Writes a `VerificationKey` into the PDA derived from `circuit_id`
+    /// so it can be shared by many subsequent `Verify` instructions. The
+    /// `vk_ic_count` header field names exactly how many IC points
+    /// follow the fixed header, and `VerificationKey::parse` rejects
+    /// anything whose length doesn't match it.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` the verification key PDA for `circuit_id`.
+    InitializeVk {
+        circuit_id: CircuitId,
+        vk: VerificationKey,
+    },
+    /// Verifies a single proof against the verification key stored in
+    /// the `circuit_id` PDA. When `nullifier_index` is set, the public
+    /// input at that index is treated as a nullifier: the instruction
+    /// fails if it has already been spent, and it is marked spent once
+    /// the proof checks out, creating the nullifier PDA first if this
+    /// is the first time it's been spent.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` the verification key PDA for `circuit_id`.
+    /// 1. `[writable]` the nullifier PDA, only if `nullifier_index` is
+    ///    `Some`.
+    /// 2. `[writable, signer]` the payer that funds the nullifier PDA's
+    ///    creation, only if `nullifier_index` is `Some`.
+    /// 3. `[]` the system program, only if `nullifier_index` is `Some`.
+    Verify {
+        circuit_id: CircuitId,
+        nullifier_index: Option<u16>,
+        proof: NoirProof,
+        public_inputs: PublicInputs,
+    },
+    /// Verifies `num_proofs` proofs against the verification key stored
+    /// in the `circuit_id` PDA in a single pairing check. `public_input_count`
+    /// is declared up front and cross-checked against the VK's own
+    /// `vk_ic` length once the processor loads it, so a batch built
+    /// against the wrong VK is rejected instead of silently misparsed.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` the verification key PDA for `circuit_id`.
+    VerifyBatch {
+        circuit_id: CircuitId,
+        num_proofs: u16,
+        public_input_count: u16,
+        proofs_data: Vec<u8>,
+    },
+    /// Verifies a proof whose single public input is a commitment the
+    /// program derives on-chain from `application_data`, rather than
+    /// one supplied verbatim by the caller. This binds an arbitrary
+    /// amount of application data to exactly one field element, the
+    /// way a Noir circuit exposes a `std::hash` public output.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` the verification key PDA for `circuit_id`.
+    /// 1. `[writable]` the nullifier PDA, only if `nullifier_index` is
+    ///    `Some`.
+    /// 2. `[writable, signer]` the payer that funds the nullifier PDA's
+    ///    creation, only if `nullifier_index` is `Some`.
+    /// 3. `[]` the system program, only if `nullifier_index` is `Some`.
+    VerifyCommitment {
+        circuit_id: CircuitId,
+        nullifier_index: Option<u16>,
+        proof: NoirProof,
+        application_data: Vec<u8>,
+    },
+}
+
+/// Reads a `u16` out of `data` at `offset` or fails with the same error
+/// used for every other header-shape problem.
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    data.get(offset..offset + 2)
+        .and_then(|b| b.try_into().ok())
+        .map(u16::from_le_bytes)
+        .ok_or_else(|| VerifierError::InstructionLengthMismatch.into())
+}
+
+/// Reads the `has_nullifier` flag byte and, if set, the `u16` nullifier
+/// index that follows it at `offset`. Returns the parsed index and how
+/// many bytes the field occupied (1 or 3), shared by every `Verify*`
+/// variant that supports replay protection.
+fn read_nullifier_header(rest: &[u8], offset: usize) -> Result<(Option<u16>, usize), ProgramError> {
+    let has_nullifier = *rest
+        .get(offset)
+        .ok_or(VerifierError::InstructionLengthMismatch)?;
+    match has_nullifier {
+        0 => Ok((None, 1)),
+        1 => Ok((Some(read_u16(rest, offset + 1)?), 3)),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+impl VerifierInstruction {
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let (&version, rest) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if version != INSTRUCTION_VERSION {
+            return Err(VerifierError::UnsupportedInstructionVersion.into());
+        }
+
+        let (&tag, rest) = rest
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        match tag {
+            0 => {
+                if rest.len() < 32 + 2 {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+                let circuit_id = rest[0..32]
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let vk_ic_count = read_u16(rest, 32)?;
+                let vk = VerificationKey::parse(&rest[34..], vk_ic_count)?;
+
+                Ok(Self::InitializeVk { circuit_id, vk })
+            }
+            1 => {
+                if rest.len() < 32 + 1 {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+                let circuit_id = rest[0..32]
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                let (nullifier_index, nullifier_header_len) = read_nullifier_header(rest, 32)?;
+
+                let count_offset = 32 + nullifier_header_len;
+                let public_input_count = read_u16(rest, count_offset)?;
+                let proof_offset = count_offset + 2;
+
+                if rest.len() != proof_offset + 256 + public_input_count as usize * 32 {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+
+                let proof = parse_proof(&rest[proof_offset..proof_offset + 256])?;
+                let public_inputs =
+                    parse_public_inputs(&rest[proof_offset + 256..], public_input_count)?;
+
+                Ok(Self::Verify {
+                    circuit_id,
+                    nullifier_index,
+                    proof,
+                    public_inputs,
+                })
+            }
+            2 => {
+                if rest.len() < 32 + 4 {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+                let circuit_id = rest[0..32]
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let num_proofs = read_u16(rest, 32)?;
+                let public_input_count = read_u16(rest, 34)?;
+                let proofs_data = &rest[36..];
+
+                let entry_len = 256 + public_input_count as usize * 32;
+                if num_proofs == 0 || proofs_data.len() != entry_len * num_proofs as usize {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+
+                Ok(Self::VerifyBatch {
+                    circuit_id,
+                    num_proofs,
+                    public_input_count,
+                    proofs_data: proofs_data.to_vec(),
+                })
+            }
+            3 => {
+                if rest.len() < 32 + 1 {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+                let circuit_id = rest[0..32]
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                let (nullifier_index, nullifier_header_len) = read_nullifier_header(rest, 32)?;
+
+                let proof_offset = 32 + nullifier_header_len;
+                if rest.len() < proof_offset + 256 {
+                    return Err(VerifierError::InstructionLengthMismatch.into());
+                }
+
+                let proof = parse_proof(&rest[proof_offset..proof_offset + 256])?;
+                let application_data = rest[proof_offset + 256..].to_vec();
+
+                Ok(Self::VerifyCommitment {
+                    circuit_id,
+                    nullifier_index,
+                    proof,
+                    application_data,
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::VK_FIXED_LEN;
+
+    fn verify_payload(circuit_id: CircuitId, nullifier_index: Option<u16>) -> Vec<u8> {
+        let mut data = vec![INSTRUCTION_VERSION, 1];
+        data.extend_from_slice(&circuit_id);
+        match nullifier_index {
+            None => data.push(0),
+            Some(index) => {
+                data.push(1);
+                data.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&0u16.to_le_bytes()); // public_input_count
+        data.extend_from_slice(&[0u8; 256]); // proof
+        data
+    }
+
+    #[test]
+    fn unpack_rejects_unsupported_version() {
+        let mut data = verify_payload([0u8; 32], None);
+        data[0] = INSTRUCTION_VERSION + 1;
+        assert!(VerifierInstruction::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn unpack_rejects_empty_data() {
+        assert!(VerifierInstruction::unpack(&[]).is_err());
+    }
+
+    #[test]
+    fn unpack_verify_without_nullifier() {
+        let data = verify_payload([7u8; 32], None);
+        match VerifierInstruction::unpack(&data).unwrap() {
+            VerifierInstruction::Verify {
+                circuit_id,
+                nullifier_index,
+                ..
+            } => {
+                assert_eq!(circuit_id, [7u8; 32]);
+                assert_eq!(nullifier_index, None);
+            }
+            _ => panic!("expected Verify"),
+        }
+    }
+
+    #[test]
+    fn unpack_verify_with_nullifier() {
+        let data = verify_payload([9u8; 32], Some(3));
+        match VerifierInstruction::unpack(&data).unwrap() {
+            VerifierInstruction::Verify {
+                nullifier_index, ..
+            } => assert_eq!(nullifier_index, Some(3)),
+            _ => panic!("expected Verify"),
+        }
+    }
+
+    #[test]
+    fn unpack_rejects_truncated_verify_payload() {
+        let mut data = verify_payload([0u8; 32], None);
+        data.pop();
+        assert!(VerifierInstruction::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn unpack_initialize_vk_round_trips_ic_count() {
+        let mut data = vec![INSTRUCTION_VERSION, 0];
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; VK_FIXED_LEN]);
+        data.extend_from_slice(&[0u8; 2 * 64]);
+
+        match VerifierInstruction::unpack(&data).unwrap() {
+            VerifierInstruction::InitializeVk { circuit_id, vk } => {
+                assert_eq!(circuit_id, [1u8; 32]);
+                assert_eq!(vk.vk_ic.len(), 2);
+            }
+            _ => panic!("expected InitializeVk"),
+        }
+    }
+
+    #[test]
+    fn unpack_initialize_vk_rejects_wrong_length_for_declared_count() {
+        let mut data = vec![INSTRUCTION_VERSION, 0];
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; VK_FIXED_LEN]);
+        data.extend_from_slice(&[0u8; 64]); // only one IC point, not two
+
+        assert!(VerifierInstruction::unpack(&data).is_err());
+    }
+
+    fn verify_batch_payload(
+        circuit_id: CircuitId,
+        num_proofs: u16,
+        public_input_count: u16,
+    ) -> Vec<u8> {
+        let mut data = vec![INSTRUCTION_VERSION, 2];
+        data.extend_from_slice(&circuit_id);
+        data.extend_from_slice(&num_proofs.to_le_bytes());
+        data.extend_from_slice(&public_input_count.to_le_bytes());
+        let entry_len = 256 + public_input_count as usize * 32;
+        data.extend_from_slice(&vec![0u8; entry_len * num_proofs as usize]);
+        data
+    }
+
+    #[test]
+    fn unpack_verify_batch_round_trips_proofs_data() {
+        let data = verify_batch_payload([3u8; 32], 2, 1);
+        match VerifierInstruction::unpack(&data).unwrap() {
+            VerifierInstruction::VerifyBatch {
+                circuit_id,
+                num_proofs,
+                public_input_count,
+                proofs_data,
+            } => {
+                assert_eq!(circuit_id, [3u8; 32]);
+                assert_eq!(num_proofs, 2);
+                assert_eq!(public_input_count, 1);
+                assert_eq!(proofs_data.len(), 2 * (256 + 32));
+            }
+            _ => panic!("expected VerifyBatch"),
+        }
+    }
+
+    #[test]
+    fn unpack_verify_batch_rejects_zero_proofs() {
+        let data = verify_batch_payload([0u8; 32], 0, 1);
+        assert!(VerifierInstruction::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn unpack_verify_batch_rejects_wrong_proofs_data_length() {
+        let mut data = verify_batch_payload([0u8; 32], 2, 1);
+        data.pop();
+        assert!(VerifierInstruction::unpack(&data).is_err());
+    }
+
+    fn verify_commitment_payload(
+        circuit_id: CircuitId,
+        nullifier_index: Option<u16>,
+        application_data: &[u8],
+    ) -> Vec<u8> {
+        let mut data = vec![INSTRUCTION_VERSION, 3];
+        data.extend_from_slice(&circuit_id);
+        match nullifier_index {
+            None => data.push(0),
+            Some(index) => {
+                data.push(1);
+                data.extend_from_slice(&index.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&[0u8; 256]); // proof
+        data.extend_from_slice(application_data);
+        data
+    }
+
+    #[test]
+    fn unpack_verify_commitment_without_nullifier() {
+        let data = verify_commitment_payload([5u8; 32], None, b"hello");
+        match VerifierInstruction::unpack(&data).unwrap() {
+            VerifierInstruction::VerifyCommitment {
+                circuit_id,
+                nullifier_index,
+                application_data,
+                ..
+            } => {
+                assert_eq!(circuit_id, [5u8; 32]);
+                assert_eq!(nullifier_index, None);
+                assert_eq!(application_data, b"hello");
+            }
+            _ => panic!("expected VerifyCommitment"),
+        }
+    }
+
+    #[test]
+    fn unpack_verify_commitment_with_nullifier() {
+        let data = verify_commitment_payload([6u8; 32], Some(4), b"");
+        match VerifierInstruction::unpack(&data).unwrap() {
+            VerifierInstruction::VerifyCommitment {
+                nullifier_index,
+                application_data,
+                ..
+            } => {
+                assert_eq!(nullifier_index, Some(4));
+                assert!(application_data.is_empty());
+            }
+            _ => panic!("expected VerifyCommitment"),
+        }
+    }
+
+    #[test]
+    fn unpack_verify_commitment_rejects_truncated_proof() {
+        let mut data = verify_commitment_payload([0u8; 32], None, b"");
+        data.truncate(data.len() - 1);
+        assert!(VerifierInstruction::unpack(&data).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::error::VerifierError;
+use crate::groth16::{self, NoirProof, PublicInputs};
+use crate::instruction::CircuitId;
+use crate::state::{VerificationKey, NULLIFIER_SEED, VK_SEED};
+
+/// Size of a nullifier PDA's data: a single spent/unspent flag byte.
+const NULLIFIER_ACCOUNT_SPACE: usize = 1;
+
+fn vk_account_matches(circuit_id: &CircuitId, program_id: &Pubkey, account: &AccountInfo) -> bool {
+    let (expected, _bump) =
+        pinocchio::pubkey::find_program_address(&[VK_SEED, circuit_id.as_slice()], program_id);
+    expected == *account.key()
+}
+
+/// Derives the nullifier PDA for `(circuit_id, nullifier)` and returns
+/// both its address and the bump seed needed to sign for it, since
+/// `spend_nullifier` may need to create the account itself the first
+/// time a given nullifier is spent.
+fn find_nullifier_address(
+    circuit_id: &CircuitId,
+    nullifier: &[u8; 32],
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    pinocchio::pubkey::find_program_address(
+        &[NULLIFIER_SEED, circuit_id.as_slice(), nullifier.as_slice()],
+        program_id,
+    )
+}
+
+/// Rejects replays of a proof whose `nullifier_index`-th public input
+/// has already been spent, then marks the matching nullifier PDA as
+/// spent. The PDA is created on demand, funded by `payer`, the first
+/// time a given nullifier is spent, so no separate setup instruction is
+/// needed to stand one up before `Verify`/`VerifyCommitment` can use it.
+fn spend_nullifier(
+    program_id: &Pubkey,
+    circuit_id: &CircuitId,
+    nullifier_index: u16,
+    public_inputs: &PublicInputs,
+    nullifier_account: &AccountInfo,
+    payer: &AccountInfo,
+) -> ProgramResult {
+    let nullifier = public_inputs
+        .0
+        .get(nullifier_index as usize)
+        .ok_or(VerifierError::NullifierIndexOutOfRange)?;
+
+    let (expected, bump) = find_nullifier_address(circuit_id, nullifier, program_id);
+    if expected != *nullifier_account.key() {
+        return Err(VerifierError::NullifierAccountMismatch.into());
+    }
+
+    if nullifier_account.data_is_empty() {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(NULLIFIER_SEED),
+            Seed::from(circuit_id.as_slice()),
+            Seed::from(nullifier.as_slice()),
+            Seed::from(&bump_seed[..]),
+        ];
+        let signer = Signer::from(&seeds[..]);
+
+        CreateAccount {
+            from: payer,
+            to: nullifier_account,
+            lamports: Rent::get()?.minimum_balance(NULLIFIER_ACCOUNT_SPACE),
+            space: NULLIFIER_ACCOUNT_SPACE as u64,
+            owner: program_id,
+        }
+        .invoke_signed(&[signer])?;
+    } else if nullifier_account.owner() != program_id || !nullifier_account.is_writable() {
+        return Err(VerifierError::NullifierAccountMismatch.into());
+    }
+
+    let mut data = nullifier_account.try_borrow_mut_data()?;
+    if data.is_empty() {
+        return Err(VerifierError::NullifierAccountMismatch.into());
+    }
+    if data[0] != 0 {
+        return Err(VerifierError::NullifierAlreadySpent.into());
+    }
+    data[0] = 1;
+
+    Ok(())
+}
+
+/// Publishes the outcome of a verification as CPI return data so a
+/// parent program that `invoke`s this one can read the result with
+/// `sol_get_return_data` and branch on it instead of relying on the
+/// whole transaction aborting.
+fn set_verification_return_data(num_public_inputs: u32) {
+    let mut payload = [0u8; 5];
+    payload[0] = 1; // success flag; `verify_proof` already errored out otherwise
+    payload[1..5].copy_from_slice(&num_public_inputs.to_le_bytes());
+    solana_program::program::set_return_data(&payload);
+}
+
+pub fn process_initialize_vk(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    circuit_id: CircuitId,
+    vk: VerificationKey,
+) -> ProgramResult {
+    let [vk_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !vk_account_matches(&circuit_id, program_id, vk_account) {
+        return Err(VerifierError::VerificationKeyAccountMismatch.into());
+    }
+
+    if vk_account.owner() != program_id || !vk_account.is_writable() {
+        return Err(VerifierError::InvalidVerificationKeyAccount.into());
+    }
+
+    // `encoded` carries its own `ic_count` prefix, so an account that's
+    // larger than this VK (e.g. sized to fit a bigger one later) can't
+    // have its leftover bytes misread as extra `vk_ic` points on the
+    // next `from_account_data`.
+    let encoded = vk.to_account_data();
+
+    let mut data = vk_account.try_borrow_mut_data()?;
+    if data.len() < encoded.len() {
+        return Err(VerifierError::InvalidVerificationKeyAccount.into());
+    }
+    data[..encoded.len()].copy_from_slice(&encoded);
+
+    msg!("Verification key initialized");
+    Ok(())
+}
+
+/// Shared tail of every single-proof `Verify*` variant: load the VK,
+/// run the pairing check, optionally spend a nullifier, and publish
+/// the return data. Only how `public_inputs` was obtained differs
+/// between variants.
+fn verify_and_finalize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    circuit_id: &CircuitId,
+    nullifier_index: Option<u16>,
+    proof: &NoirProof,
+    public_inputs: &PublicInputs,
+) -> ProgramResult {
+    let [vk_account, rest @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !vk_account_matches(circuit_id, program_id, vk_account) {
+        return Err(VerifierError::VerificationKeyAccountMismatch.into());
+    }
+
+    let data = vk_account.try_borrow_data()?;
+    let vk = VerificationKey::from_account_data(&data)?;
+
+    let prepared_inputs = groth16::prepare_public_inputs(public_inputs, &vk)?;
+    groth16::verify_proof(proof, &prepared_inputs, &vk)?;
+
+    if let Some(nullifier_index) = nullifier_index {
+        let [nullifier_account, payer, ..] = rest else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        spend_nullifier(
+            program_id,
+            circuit_id,
+            nullifier_index,
+            public_inputs,
+            nullifier_account,
+            payer,
+        )?;
+    }
+
+    set_verification_return_data(public_inputs.0.len() as u32);
+    Ok(())
+}
+
+pub fn process_verify(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    circuit_id: CircuitId,
+    nullifier_index: Option<u16>,
+    proof: NoirProof,
+    public_inputs: PublicInputs,
+) -> ProgramResult {
+    verify_and_finalize(
+        program_id,
+        accounts,
+        &circuit_id,
+        nullifier_index,
+        &proof,
+        &public_inputs,
+    )?;
+
+    msg!("Proof verified successfully!");
+    Ok(())
+}
+
+pub fn process_verify_commitment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    circuit_id: CircuitId,
+    nullifier_index: Option<u16>,
+    proof: NoirProof,
+    application_data: Vec<u8>,
+) -> ProgramResult {
+    // The single public input is never taken from the instruction
+    // directly; it's derived on-chain from `application_data`, so a
+    // caller can't submit a valid proof alongside data it doesn't
+    // actually match.
+    let public_inputs = groth16::commitment_public_inputs(&application_data);
+
+    verify_and_finalize(
+        program_id,
+        accounts,
+        &circuit_id,
+        nullifier_index,
+        &proof,
+        &public_inputs,
+    )?;
+
+    msg!("Commitment verified successfully!");
+    Ok(())
+}
+
+pub fn process_verify_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    circuit_id: CircuitId,
+    num_proofs: u16,
+    public_input_count: u16,
+    proofs_data: Vec<u8>,
+) -> ProgramResult {
+    let [vk_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !vk_account_matches(&circuit_id, program_id, vk_account) {
+        return Err(VerifierError::VerificationKeyAccountMismatch.into());
+    }
+
+    let data = vk_account.try_borrow_data()?;
+    let vk = VerificationKey::from_account_data(&data)?;
+    let num_public_inputs = vk.vk_ic.len() - 1;
+
+    // The batch was built against a declared input count; make sure it
+    // actually matches this VK instead of silently verifying the wrong
+    // number of public inputs per proof.
+    if public_input_count as usize != num_public_inputs {
+        return Err(VerifierError::PublicInputCountMismatch.into());
+    }
+
+    let (proofs, public_inputs) =
+        groth16::split_batch(&proofs_data, num_proofs, num_public_inputs)?;
+    groth16::verify_batch(&proofs, &public_inputs, &vk)?;
+
+    set_verification_return_data((num_public_inputs * proofs.len()) as u32);
+
+    msg!("Batch verified successfully!");
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::error::VerifierError;
+
+/// Fixed-size portion of a Groth16 verification key: alpha_g1, beta_g2,
+/// gamma_g2 and delta_g2. The `vk_ic` vector is variable-length and is
+/// appended after these bytes wherever a `VerificationKey` is serialized.
+pub const VK_FIXED_LEN: usize = 64 + 128 + 128 + 128;
+
+/// Size of the `ic_count` prefix an account's data starts with. Storing
+/// the count explicitly means `from_account_data` never has to guess it
+/// from how much data happens to follow the fixed header.
+pub const VK_IC_COUNT_LEN: usize = 2;
+
+/// The seed prefix used to derive a verification key PDA from a
+/// circuit identifier.
+pub const VK_SEED: &[u8] = b"vk";
+
+/// The seed prefix used to derive a nullifier PDA from a circuit
+/// identifier and the nullifying public input.
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+
+pub struct VerificationKey {
+    pub vk_alpha_g1: [u8; 64],
+    pub vk_beta_g2: [u8; 128],
+    pub vk_gamma_g2: [u8; 128],
+    pub vk_delta_g2: [u8; 128],
+    pub vk_ic: Vec<[u8; 64]>,
+}
+
+impl VerificationKey {
+    /// Builds a `VerificationKey` out of the fixed group elements
+    /// followed by exactly `ic_count` IC points, with no stored count
+    /// of its own. Used to parse `InitializeVk`'s instruction data,
+    /// where the count arrives as a separate header field rather than a
+    /// prefix baked into these bytes.
+    pub fn parse(data: &[u8], ic_count: u16) -> Result<Self, ProgramError> {
+        if ic_count == 0 {
+            return Err(VerifierError::InvalidVerificationKeyAccount.into());
+        }
+        let ic_count = ic_count as usize;
+        if data.len() != VK_FIXED_LEN + ic_count * 64 {
+            return Err(VerifierError::InstructionLengthMismatch.into());
+        }
+
+        let vk_alpha_g1 = data[0..64]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let vk_beta_g2 = data[64..192]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let vk_gamma_g2 = data[192..320]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let vk_delta_g2 = data[320..448]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let vk_ic = data[VK_FIXED_LEN..]
+            .chunks(64)
+            .map(|chunk| {
+                chunk
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)
+            })
+            .collect::<Result<Vec<[u8; 64]>, ProgramError>>()?;
+
+        Ok(Self {
+            vk_alpha_g1,
+            vk_beta_g2,
+            vk_gamma_g2,
+            vk_delta_g2,
+            vk_ic,
+        })
+    }
+
+    /// Deserializes a `VerificationKey` previously written by
+    /// `InitializeVk` out of an account's raw data. `vk_ic`'s length
+    /// comes from the `ic_count` prefix `to_account_data` writes ahead
+    /// of the fixed header, not from how much data the account happens
+    /// to hold beyond it, so re-initializing a PDA with a smaller VK or
+    /// over-allocating it for rent-exemption headroom can't leak stale
+    /// or padding bytes in as bogus extra IC points.
+    pub fn from_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < VK_IC_COUNT_LEN + VK_FIXED_LEN {
+            return Err(VerifierError::InvalidVerificationKeyAccount.into());
+        }
+
+        let ic_count = u16::from_le_bytes(
+            data[0..VK_IC_COUNT_LEN]
+                .try_into()
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        ) as usize;
+        if ic_count == 0 {
+            return Err(VerifierError::InvalidVerificationKeyAccount.into());
+        }
+
+        let fixed_start = VK_IC_COUNT_LEN;
+        let ic_start = fixed_start + VK_FIXED_LEN;
+        let ic_end = ic_start + ic_count * 64;
+        if data.len() < ic_end {
+            return Err(VerifierError::InvalidVerificationKeyAccount.into());
+        }
+
+        let vk_alpha_g1 = data[fixed_start..fixed_start + 64]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let vk_beta_g2 = data[fixed_start + 64..fixed_start + 192]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let vk_gamma_g2 = data[fixed_start + 192..fixed_start + 320]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let vk_delta_g2 = data[fixed_start + 320..fixed_start + 448]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        let vk_ic = data[ic_start..ic_end]
+            .chunks(64)
+            .map(|chunk| {
+                chunk
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidAccountData)
+            })
+            .collect::<Result<Vec<[u8; 64]>, ProgramError>>()?;
+
+        Ok(Self {
+            vk_alpha_g1,
+            vk_beta_g2,
+            vk_gamma_g2,
+            vk_delta_g2,
+            vk_ic,
+        })
+    }
+
+    /// Serializes this key the same way `from_account_data` expects to
+    /// read it back: a 2-byte `ic_count` prefix, the fixed header, then
+    /// the `vk_ic` points.
+    pub fn to_account_data(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(VK_IC_COUNT_LEN + VK_FIXED_LEN + self.vk_ic.len() * 64);
+        out.extend_from_slice(&(self.vk_ic.len() as u16).to_le_bytes());
+        out.extend_from_slice(&self.vk_alpha_g1);
+        out.extend_from_slice(&self.vk_beta_g2);
+        out.extend_from_slice(&self.vk_gamma_g2);
+        out.extend_from_slice(&self.vk_delta_g2);
+        for ic in &self.vk_ic {
+            out.extend_from_slice(ic);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_vk(ic_count: usize) -> VerificationKey {
+        VerificationKey {
+            vk_alpha_g1: [1u8; 64],
+            vk_beta_g2: [2u8; 128],
+            vk_gamma_g2: [3u8; 128],
+            vk_delta_g2: [4u8; 128],
+            vk_ic: (0..ic_count).map(|i| [i as u8; 64]).collect(),
+        }
+    }
+
+    #[test]
+    fn account_data_round_trips_through_ic_count() {
+        let vk = sample_vk(3);
+        let encoded = vk.to_account_data();
+        let decoded = VerificationKey::from_account_data(&encoded).unwrap();
+
+        assert_eq!(decoded.vk_alpha_g1, vk.vk_alpha_g1);
+        assert_eq!(decoded.vk_beta_g2, vk.vk_beta_g2);
+        assert_eq!(decoded.vk_gamma_g2, vk.vk_gamma_g2);
+        assert_eq!(decoded.vk_delta_g2, vk.vk_delta_g2);
+        assert_eq!(decoded.vk_ic, vk.vk_ic);
+    }
+
+    #[test]
+    fn account_data_ignores_trailing_padding() {
+        // A VK account sized to fit a larger key later still round-trips
+        // the smaller key it currently holds, instead of misreading the
+        // padding as extra `vk_ic` points.
+        let vk = sample_vk(2);
+        let mut encoded = vk.to_account_data();
+        encoded.extend_from_slice(&[0xffu8; 3 * 64]);
+
+        let decoded = VerificationKey::from_account_data(&encoded).unwrap();
+        assert_eq!(decoded.vk_ic, vk.vk_ic);
+    }
+
+    #[test]
+    fn account_data_rejects_truncated_ic_points() {
+        let vk = sample_vk(2);
+        let encoded = vk.to_account_data();
+        let truncated = &encoded[..encoded.len() - 1];
+
+        assert!(VerificationKey::from_account_data(truncated).is_err());
+    }
+
+    #[test]
+    fn account_data_rejects_zero_ic_count() {
+        let mut encoded = sample_vk(1).to_account_data();
+        encoded[0..2].copy_from_slice(&0u16.to_le_bytes());
+
+        assert!(VerificationKey::from_account_data(&encoded).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_length_mismatched_with_ic_count() {
+        let data = vec![0u8; VK_FIXED_LEN + 64];
+        assert!(VerificationKey::parse(&data, 2).is_err());
+        assert!(VerificationKey::parse(&data, 1).is_ok());
+    }
+}